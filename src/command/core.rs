@@ -1,9 +1,10 @@
 use super::handlers::get_timestamp;
 use crate::{
     common::parse_string_args,
-    protocol::{Data, RedisArray},
+    protocol::Data,
     store::{coords::Point, value::Value},
 };
+use bytes::Bytes;
 use rust_decimal::Decimal;
 
 #[derive(Clone)]
@@ -15,9 +16,13 @@ pub enum Command {
         key: String,
         value: Value,
         expiry: Option<u64>,
-        raw_command: String,
+        raw_command: Bytes,
     },
     ConfigGet(String),
+    ConfigSet {
+        key: String,
+        value: String,
+    },
     Keys(String),
     Info,
     Psync(String, String),
@@ -45,7 +50,7 @@ pub enum Command {
     },
     Incr {
         key: String,
-        raw_command: String,
+        raw_command: Bytes,
     },
     Multi,
     Exec,
@@ -55,7 +60,7 @@ pub enum Command {
         key: String,
         values: Vec<String>,
         is_left: bool,
-        raw_command: String,
+        raw_command: Bytes,
     },
     LRange {
         key: String,
@@ -133,17 +138,25 @@ impl From<&[Data]> for Command {
                 key: key.into(),
                 value: value.to_string().into(),
                 expiry: get_timestamp(expiry_ms),
-                raw_command: get_raw_array_command(val),
+                raw_command: Bytes::new(),
             },
             ("SET", [Data::BStr(key), Data::BStr(value)]) => Command::Set {
                 key: key.into(),
                 value: value.to_string().into(),
                 expiry: None,
-                raw_command: get_raw_array_command(val),
+                raw_command: Bytes::new(),
             },
             ("CONFIG", [Data::BStr(arg), Data::BStr(key)]) if arg.eq_ignore_ascii_case("GET") => {
                 Command::ConfigGet(key.into())
             }
+            ("CONFIG", [Data::BStr(arg), Data::BStr(key), Data::BStr(value)])
+                if arg.eq_ignore_ascii_case("SET") =>
+            {
+                Command::ConfigSet {
+                    key: key.into(),
+                    value: value.into(),
+                }
+            }
             ("KEYS", [Data::BStr(pattern)]) => Command::Keys(pattern.into()),
             ("INFO", [Data::BStr(section)]) if section.eq_ignore_ascii_case("REPLICATION") => {
                 Command::Info
@@ -187,20 +200,20 @@ impl From<&[Data]> for Command {
             ("XREAD", ..) => parse_xread(val),
             ("INCR", [Data::BStr(key)]) => Command::Incr {
                 key: key.into(),
-                raw_command: get_raw_array_command(val),
+                raw_command: Bytes::new(),
             },
             ("MULTI", ..) => Command::Multi,
             ("EXEC", ..) => Command::Exec,
             ("DISCARD", ..) => Command::Discard,
             ("RPUSH", [Data::BStr(key), ..]) => Command::ListPush {
                 key: key.into(),
-                raw_command: get_raw_array_command(val),
+                raw_command: Bytes::new(),
                 values: parse_string_args(&val[2..]),
                 is_left: false,
             },
             ("LPUSH", [Data::BStr(key), ..]) => Command::ListPush {
                 key: key.into(),
-                raw_command: get_raw_array_command(val),
+                raw_command: Bytes::new(),
                 values: parse_string_args(&val[2..]),
                 is_left: true,
             },
@@ -224,9 +237,9 @@ impl From<&[Data]> for Command {
                 Self::Publish(channel.to_string(), message.to_string())
             }
             ("ZADD", [Data::BStr(key), Data::BStr(score), Data::BStr(member)]) => Self::ZAdd {
-                key: key.clone(),
+                key: key.into(),
                 score: Decimal::from_str_exact(score).unwrap_or_default(),
-                member: member.clone(),
+                member: member.into(),
             },
             ("ZRANK", [Data::BStr(key), Data::BStr(member)]) => Self::ZRank {
                 key: key.into(),
@@ -289,6 +302,24 @@ impl Command {
             Command::XRead { block: Some(_), .. } | Command::BLPop(..)
         )
     }
+
+    /// Builds a command from a freshly-parsed frame, using the exact
+    /// on-wire bytes the reader already consumed as `raw_command`
+    /// instead of re-serializing the parsed `Data` back into RESP.
+    pub fn from_frame(data: Data, raw: Bytes) -> Command {
+        let mut command = Command::from(data);
+        command.set_raw_command(raw);
+        command
+    }
+
+    fn set_raw_command(&mut self, raw: Bytes) {
+        match self {
+            Command::Set { raw_command, .. }
+            | Command::Incr { raw_command, .. }
+            | Command::ListPush { raw_command, .. } => *raw_command = raw,
+            _ => {}
+        }
+    }
 }
 
 fn is_number(val: &str) -> bool {
@@ -333,7 +364,3 @@ fn parse_blpop(val: &[Data]) -> Command {
     };
     Command::BLPop(keys, block as u64)
 }
-
-fn get_raw_array_command(val: &[Data]) -> String {
-    RedisArray(val.to_vec()).into()
-}
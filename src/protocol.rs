@@ -0,0 +1,274 @@
+use bytes::{Bytes, BytesMut};
+use std::{fmt, str};
+
+/// Upper bound on a single bulk string's declared length, mirroring real
+/// Redis's `proto-max-bulk-len` default. Caps how much a client can make
+/// us buffer for one frame before we've even seen the payload, closing
+/// off a `$2000000000\r\n` + trickle memory-exhaustion vector.
+const MAX_BULK_LEN: i64 = 512 * 1024 * 1024;
+
+/// Upper bound on a single array frame's declared element count, for the
+/// same reason as `MAX_BULK_LEN`.
+const MAX_ARRAY_LEN: i64 = 1024 * 1024;
+
+/// A parsed RESP value. Bulk and simple strings borrow from the
+/// underlying receive buffer via a cheap `Bytes` slice rather than
+/// allocating a fresh `String`, so a command that's just forwarded on
+/// (propagation, a pipelined read) never gets copied.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Data {
+    BStr(BStr),
+    Array(Vec<Data>),
+    Null,
+}
+
+/// A RESP bulk/simple string backed by a `Bytes` slice. Derefs to `&str`
+/// so existing call sites (`.to_uppercase()`, `.parse()`, `.eq_ignore_ascii_case()`)
+/// keep working unchanged.
+///
+/// RESP bulk strings are binary-safe, but most commands treat them as
+/// text (key names, values rendered back to clients as strings), so
+/// `new` eagerly replaces any invalid UTF-8 with the standard
+/// replacement character instead of silently losing the payload to an
+/// empty string. The original invalid bytes are not kept: `as_bytes()`
+/// returns the same replaced UTF-8 that the `Deref<Target = str>` view
+/// shows. The exact on-wire bytes are only in the raw frame returned by
+/// `parse_frame`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BStr(Bytes);
+
+impl BStr {
+    fn new(bytes: Bytes) -> Self {
+        match str::from_utf8(&bytes) {
+            Ok(_) => Self(bytes),
+            Err(_) => Self(Bytes::from(String::from_utf8_lossy(&bytes).into_owned())),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.0).expect("BStr::new guarantees valid UTF-8")
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for BStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for BStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<str> for BStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for BStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl From<&BStr> for String {
+    fn from(val: &BStr) -> Self {
+        val.as_str().to_string()
+    }
+}
+
+impl From<BStr> for String {
+    fn from(val: BStr) -> Self {
+        val.as_str().to_string()
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    /// The buffer doesn't yet hold a complete frame; wait for more data.
+    Incomplete,
+    Invalid(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Incomplete => write!(f, "incomplete frame"),
+            ParseError::Invalid(msg) => write!(f, "invalid frame: {msg}"),
+        }
+    }
+}
+
+/// Attempts to pull one complete RESP frame out of `buf`. On success, the
+/// consumed bytes are split off the front of `buf` and returned as a
+/// zero-copy `Bytes` view alongside the parsed `Data`. Returns `Ok(None)`
+/// if `buf` doesn't hold a full frame yet, leaving it untouched so the
+/// caller can read more off the socket and retry without losing progress.
+pub fn parse_frame(buf: &mut BytesMut) -> Result<Option<(Data, Bytes)>, ParseError> {
+    let len = match frame_len(&buf[..], 0) {
+        Ok(len) => len,
+        Err(ParseError::Incomplete) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let raw = buf.split_to(len).freeze();
+    let mut pos = 0;
+    let data = parse_value(&raw, &mut pos)?;
+    Ok(Some((data, raw)))
+}
+
+fn find_crlf(buf: &[u8], start: usize) -> Option<usize> {
+    buf[start..].windows(2).position(|w| w == b"\r\n").map(|i| start + i)
+}
+
+fn frame_len(buf: &[u8], pos: usize) -> Result<usize, ParseError> {
+    if pos >= buf.len() {
+        return Err(ParseError::Incomplete);
+    }
+    match buf[pos] {
+        b'*' => {
+            let line_end = find_crlf(buf, pos + 1).ok_or(ParseError::Incomplete)?;
+            let count = parse_header_int(buf, pos + 1, line_end)?;
+            let mut cursor = line_end + 2;
+            if count < 0 {
+                return Ok(cursor);
+            }
+            if count > MAX_ARRAY_LEN {
+                return Err(ParseError::Invalid(format!(
+                    "array length {count} exceeds {MAX_ARRAY_LEN}"
+                )));
+            }
+            for _ in 0..count {
+                cursor = frame_len(buf, cursor)?;
+            }
+            Ok(cursor)
+        }
+        b'$' => {
+            let line_end = find_crlf(buf, pos + 1).ok_or(ParseError::Incomplete)?;
+            let len = parse_header_int(buf, pos + 1, line_end)?;
+            if len < 0 {
+                return Ok(line_end + 2);
+            }
+            if len > MAX_BULK_LEN {
+                return Err(ParseError::Invalid(format!(
+                    "bulk length {len} exceeds {MAX_BULK_LEN}"
+                )));
+            }
+            let data_end = line_end + 2 + len as usize;
+            if buf.len() < data_end + 2 {
+                return Err(ParseError::Incomplete);
+            }
+            Ok(data_end + 2)
+        }
+        b'+' | b':' | b'-' => {
+            let line_end = find_crlf(buf, pos + 1).ok_or(ParseError::Incomplete)?;
+            Ok(line_end + 2)
+        }
+        other => Err(ParseError::Invalid(format!("unexpected byte: {other}"))),
+    }
+}
+
+fn parse_header_int(buf: &[u8], start: usize, end: usize) -> Result<i64, ParseError> {
+    str::from_utf8(&buf[start..end])
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| ParseError::Invalid("malformed length header".into()))
+}
+
+fn parse_value(buf: &Bytes, pos: &mut usize) -> Result<Data, ParseError> {
+    let byte = buf[*pos];
+    match byte {
+        b'*' => {
+            let line_end = find_crlf(buf, *pos + 1).ok_or(ParseError::Incomplete)?;
+            let count = parse_header_int(buf, *pos + 1, line_end)?;
+            *pos = line_end + 2;
+            if count < 0 {
+                return Ok(Data::Null);
+            }
+            let mut items = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                items.push(parse_value(buf, pos)?);
+            }
+            Ok(Data::Array(items))
+        }
+        b'$' => {
+            let line_end = find_crlf(buf, *pos + 1).ok_or(ParseError::Incomplete)?;
+            let len = parse_header_int(buf, *pos + 1, line_end)?;
+            *pos = line_end + 2;
+            if len < 0 {
+                return Ok(Data::Null);
+            }
+            let start = *pos;
+            let end = start + len as usize;
+            *pos = end + 2;
+            Ok(Data::BStr(BStr::new(buf.slice(start..end))))
+        }
+        b'+' | b'-' => {
+            let line_end = find_crlf(buf, *pos + 1).ok_or(ParseError::Incomplete)?;
+            let slice = buf.slice(*pos + 1..line_end);
+            *pos = line_end + 2;
+            Ok(Data::BStr(BStr::new(slice)))
+        }
+        b':' => {
+            let line_end = find_crlf(buf, *pos + 1).ok_or(ParseError::Incomplete)?;
+            let slice = buf.slice(*pos + 1..line_end);
+            *pos = line_end + 2;
+            Ok(Data::BStr(BStr::new(slice)))
+        }
+        other => Err(ParseError::Invalid(format!("unexpected byte: {other}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_complete_array_frame() {
+        let mut buf = BytesMut::from(&b"*2\r\n$4\r\nECHO\r\n$2\r\nhi\r\n"[..]);
+        let (data, raw) = parse_frame(&mut buf).unwrap().unwrap();
+        assert_eq!(raw.as_ref(), &b"*2\r\n$4\r\nECHO\r\n$2\r\nhi\r\n"[..]);
+        match data {
+            Data::Array(items) => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[0], Data::BStr(BStr::new(Bytes::from_static(b"ECHO"))));
+                assert_eq!(items[1], Data::BStr(BStr::new(Bytes::from_static(b"hi"))));
+            }
+            other => panic!("expected an array, got {other:?}"),
+        }
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn returns_none_when_frame_is_split_across_reads() {
+        let mut buf = BytesMut::from(&b"*1\r\n$4\r\nPI"[..]);
+        assert!(parse_frame(&mut buf).unwrap().is_none());
+        // The partial frame is left untouched for the next read to extend.
+        buf.extend_from_slice(b"NG\r\n");
+        let (data, _) = parse_frame(&mut buf).unwrap().unwrap();
+        assert_eq!(
+            data,
+            Data::Array(vec![Data::BStr(BStr::new(Bytes::from_static(b"PING")))])
+        );
+    }
+
+    #[test]
+    fn rejects_a_bulk_length_over_the_max() {
+        let mut buf = BytesMut::from(&b"$2000000000\r\n"[..]);
+        assert!(matches!(parse_frame(&mut buf), Err(ParseError::Invalid(_))));
+    }
+
+    #[test]
+    fn bstr_replaces_invalid_utf8_instead_of_going_empty() {
+        let s = BStr::new(Bytes::from_static(&[0xff, 0xfe]));
+        assert!(!s.as_str().is_empty());
+    }
+}
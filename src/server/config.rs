@@ -0,0 +1,102 @@
+use super::tls::TlsSettings;
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// In-memory view of the server's redis.conf-style settings. Keys without
+/// a dedicated field (e.g. `replicaof`), whether read from the file or
+/// set at runtime via `CONFIG SET`, are kept in `extra`.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    pub dir: String,
+    pub dbfilename: String,
+    pub maxmemory: u64,
+    pub appendonly: bool,
+    pub tls: TlsSettings,
+    pub extra: HashMap<String, String>,
+}
+
+impl Config {
+    /// Parses a redis.conf-style file: one `key value` pair per line,
+    /// blank lines and `#` comments ignored.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let mut config = Config::default();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(char::is_whitespace) else {
+                continue;
+            };
+            config.set(key.trim(), value.trim());
+        }
+        Ok(config)
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        match key.to_lowercase().as_str() {
+            "dir" => Some(self.dir.clone()),
+            "dbfilename" => Some(self.dbfilename.clone()),
+            "maxmemory" => Some(self.maxmemory.to_string()),
+            "appendonly" => Some(if self.appendonly { "yes" } else { "no" }.to_string()),
+            "tls-enabled" => Some(if self.tls.enabled { "yes" } else { "no" }.to_string()),
+            other => self.extra.get(other).cloned(),
+        }
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        match key.to_lowercase().as_str() {
+            "dir" => self.dir = value.to_string(),
+            "dbfilename" => self.dbfilename = value.to_string(),
+            "maxmemory" => self.maxmemory = value.parse().unwrap_or(self.maxmemory),
+            "appendonly" => self.appendonly = value.eq_ignore_ascii_case("yes"),
+            "tls-enabled" => self.tls.enabled = value.eq_ignore_ascii_case("yes"),
+            "tls-cert-file" => self.tls.cert_path = Some(PathBuf::from(value)),
+            "tls-key-file" => self.tls.key_path = Some(PathBuf::from(value)),
+            "tls-ca-cert-file" => self.tls.ca_path = Some(PathBuf::from(value)),
+            other => {
+                self.extra.insert(other.to_string(), value.to_string());
+            }
+        }
+    }
+
+    /// Serializes back to the same `key value` per line format it was
+    /// loaded from, so a reload of the saved file round-trips.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = String::new();
+        out.push_str(&format!("dir {}\n", self.dir));
+        out.push_str(&format!("dbfilename {}\n", self.dbfilename));
+        out.push_str(&format!("maxmemory {}\n", self.maxmemory));
+        out.push_str(&format!("appendonly {}\n", if self.appendonly { "yes" } else { "no" }));
+        out.push_str(&format!(
+            "tls-enabled {}\n",
+            if self.tls.enabled { "yes" } else { "no" }
+        ));
+        if let Some(cert_path) = &self.tls.cert_path {
+            out.push_str(&format!("tls-cert-file {}\n", cert_path.display()));
+        }
+        if let Some(key_path) = &self.tls.key_path {
+            out.push_str(&format!("tls-key-file {}\n", key_path.display()));
+        }
+        if let Some(ca_path) = &self.tls.ca_path {
+            out.push_str(&format!("tls-ca-cert-file {}\n", ca_path.display()));
+        }
+        for (key, value) in &self.extra {
+            out.push_str(&format!("{key} {value}\n"));
+        }
+        let mut file = fs::File::create(path)?;
+        file.write_all(out.as_bytes())
+    }
+}
+
+/// Reads a single key out of a live config snapshot. Kept as a free
+/// function so `CONFIG GET` call sites don't need to know about the
+/// `Config` type's field layout.
+pub fn get_config_value(config: &Config, key: &str) -> Option<String> {
+    config.get(key)
+}
@@ -0,0 +1,48 @@
+use super::config::Config;
+use std::{path::PathBuf, sync::Arc, time::SystemTime};
+use tokio::sync::RwLock;
+
+/// Polls the config file for modifications and atomically swaps the
+/// reloaded `Config` into the shared handle so running connections pick
+/// up the change without a restart.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    config: Arc<RwLock<Config>>,
+    poll_interval: std::time::Duration,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: PathBuf, config: Arc<RwLock<Config>>) -> Self {
+        Self {
+            path,
+            config,
+            poll_interval: std::time::Duration::from_secs(1),
+        }
+    }
+
+    /// Spawns the watcher as a background task and returns its handle.
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move { self.run().await })
+    }
+
+    async fn run(self) {
+        let mut last_modified = self.modified_at();
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            let modified = self.modified_at();
+            if modified.is_none() || modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+            match Config::load(&self.path) {
+                Ok(reloaded) => *self.config.write().await = reloaded,
+                Err(e) => eprintln!("config reload failed: {e}"),
+            }
+        }
+    }
+
+    fn modified_at(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path).and_then(|m| m.modified()).ok()
+    }
+}
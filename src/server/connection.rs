@@ -0,0 +1,17 @@
+use super::{context::ServerContext, stream_reader::StreamReader};
+use crate::command::core::Command;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// Reads and dispatches commands off a client connection until it closes.
+///
+/// Builds each `Command` via `Command::from_frame` so `raw_command` is
+/// the exact on-wire bytes `StreamReader::next_frame` already captured,
+/// rather than re-serializing the parsed `Data` back into RESP.
+pub async fn handle_connection<S>(context: ServerContext, mut reader: StreamReader<S>)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    while let Ok(Some((data, raw))) = reader.next_frame().await {
+        context.execute_command(Command::from_frame(data, raw)).await;
+    }
+}
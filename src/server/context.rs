@@ -1,5 +1,6 @@
 use super::{
     replica::{replica_stream_handler, ReplicaManager, ReplicaState},
+    replica_link::{LinkState, ReplicationLink},
     stream_reader::StreamReader,
 };
 use crate::{
@@ -12,13 +13,16 @@ use crate::{
         },
         stream_handlers,
     },
-    server::{config, state::ServerState},
+    server::{
+        config::{self, Config},
+        state::ServerState,
+    },
     store::{core::InMemoryStore, list::blpop_handler},
 };
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tokio::{
-    net::TcpStream,
-    sync::{mpsc, Mutex},
+    io::{AsyncRead, AsyncWrite},
+    sync::{mpsc, Mutex, RwLock},
 };
 
 #[derive(Clone, Default)]
@@ -27,6 +31,13 @@ pub struct ServerContext {
     pub state: Arc<Mutex<ServerState>>,
     pub replicas: Arc<Mutex<ReplicaManager>>,
     pub channels: crate::channel::ChannelManager,
+    pub config: Arc<RwLock<Config>>,
+    pub config_path: PathBuf,
+    /// This node's view of its link to its replication master, if it has
+    /// one. Updated by `ReplicaClient` as the handshake progresses and
+    /// read back out by `info_replication` to report link state, last
+    /// error, and retry count in the `INFO replication` section.
+    pub replication_link: Arc<RwLock<ReplicationLink>>,
 }
 
 impl ServerContext {
@@ -45,14 +56,25 @@ impl ServerContext {
                 self.propagate(raw_command).await;
                 sstring_response("OK")
             }
-            Command::ConfigGet(key) => match config::get_config_value(&key) {
-                Some(value) => array_response(vec![key, value]),
-                _ => null_response(),
-            },
+            Command::ConfigGet(key) => {
+                let config = self.config.read().await;
+                match config::get_config_value(&config, &key) {
+                    Some(value) => array_response(vec![key, value]),
+                    _ => null_response(),
+                }
+            }
+            Command::ConfigSet { key, value } => {
+                let mut config = self.config.write().await;
+                config.set(&key, &value);
+                if let Err(e) = config.save(&self.config_path) {
+                    eprintln!("failed to persist config: {e}");
+                }
+                sstring_response("OK")
+            }
             Command::Keys(pattern) => {
                 CommandResponse::Single(handlers::keys(&pattern, &self.store).await)
             }
-            Command::Info => CommandResponse::Single(handlers::info(self).await),
+            Command::Info => bstring_response(&self.info_replication().await),
             Command::Replconf => sstring_response("OK"),
             Command::ReplconfGetAck(_) => CommandResponse::ReplconfAck,
             Command::Wait {
@@ -173,8 +195,11 @@ impl ServerContext {
         CommandResponse::Multiple(responses)
     }
 
-    pub async fn add_replica(&self, reader: StreamReader<TcpStream>) {
-        let (tx, rx) = mpsc::channel::<Vec<u8>>(100);
+    pub async fn add_replica<S>(&self, reader: StreamReader<S>)
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel::<bytes::Bytes>(100);
         let replica_id = uuid::Uuid::new_v4().to_string();
         let replica_state = Arc::new(Mutex::new(ReplicaState::new(tx.clone())));
         let state_clone = replica_state.clone();
@@ -191,11 +216,32 @@ impl ServerContext {
         });
     }
 
-    async fn propagate(&self, command: String) {
-        self.replicas
-            .lock()
-            .await
-            .broadcast(command.into_bytes())
-            .await;
+    async fn propagate(&self, command: bytes::Bytes) {
+        self.replicas.lock().await.broadcast(command).await;
+    }
+
+    /// Builds the `INFO replication` section: the canonical body from
+    /// `handlers::info` (role, `master_replid`, `master_repl_offset`,
+    /// etc.), with this node's own `replication_link` state appended
+    /// when it's a replica, so link state/last error/retry count are
+    /// visible without disturbing what a master already reports.
+    async fn info_replication(&self) -> String {
+        let mut info = handlers::info(self).await;
+        if self.config.read().await.extra.contains_key("replicaof") {
+            let link = self.replication_link.read().await;
+            let link_state = match link.state {
+                LinkState::Down => "down",
+                LinkState::Connecting => "connecting",
+                LinkState::Syncing => "sync",
+                LinkState::Connected => "up",
+            };
+            info.push_str(&format!("master_link_status:{link_state}\r\n"));
+            info.push_str(&format!(
+                "master_last_io_error:{}\r\n",
+                link.last_error.as_deref().unwrap_or("")
+            ));
+            info.push_str(&format!("master_link_down_retry_count:{}\r\n", link.retry_count));
+        }
+        info
     }
 }
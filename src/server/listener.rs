@@ -0,0 +1,76 @@
+use super::{
+    config::Config,
+    config_watcher::ConfigWatcher,
+    connection::handle_connection,
+    context::ServerContext,
+    replica_client::ReplicaClient,
+    stream_reader::StreamReader,
+    tls::MaybeTlsStream,
+};
+use std::{io, path::PathBuf, sync::Arc};
+use tokio::{net::TcpListener, sync::RwLock};
+
+/// Server entry point: loads config from disk, starts the hot-reload
+/// watcher so edits to the file (or a `CONFIG SET`) show up in the live
+/// config without a restart, then accepts and serves client connections —
+/// over TLS when the config enables it, plain TCP otherwise. If the config
+/// names a `replicaof` master, also starts a `ReplicaClient` to replicate
+/// from it. The TLS settings and `replicaof` are read only here, at
+/// startup; a later reload does not change them.
+pub async fn run(config_path: PathBuf, bind_addr: &str) -> io::Result<()> {
+    let config = Config::load(&config_path).unwrap_or_default();
+    let config = Arc::new(RwLock::new(config));
+    ConfigWatcher::new(config_path.clone(), config.clone()).spawn();
+
+    let acceptor = config.read().await.tls.build_acceptor()?;
+    let connector = config.read().await.tls.build_connector()?;
+
+    let context = ServerContext {
+        config: config.clone(),
+        config_path,
+        ..Default::default()
+    };
+
+    let listening_port: u16 = bind_addr
+        .rsplit_once(':')
+        .and_then(|(_, port)| port.parse().ok())
+        .unwrap_or(6379);
+    if let Some(master_addr) = config
+        .read()
+        .await
+        .extra
+        .get("replicaof")
+        .map(|target| target.replace(' ', ":"))
+    {
+        ReplicaClient::new(
+            master_addr,
+            listening_port,
+            context.clone(),
+            context.replication_link.clone(),
+            connector,
+        )
+        .spawn();
+    }
+
+    let listener = TcpListener::bind(bind_addr).await?;
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let context = context.clone();
+        let acceptor = acceptor.clone();
+        tokio::spawn(async move {
+            match acceptor {
+                Some(acceptor) => match acceptor.accept(stream).await {
+                    Ok(tls_stream) => {
+                        let stream = MaybeTlsStream::TlsServer(Box::new(tls_stream));
+                        handle_connection(context, StreamReader::new(stream)).await;
+                    }
+                    Err(e) => eprintln!("tls handshake with client failed: {e}"),
+                },
+                None => {
+                    let stream = MaybeTlsStream::Plain(stream);
+                    handle_connection(context, StreamReader::new(stream)).await;
+                }
+            }
+        });
+    }
+}
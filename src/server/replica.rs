@@ -0,0 +1,57 @@
+use super::stream_reader::StreamReader;
+use bytes::Bytes;
+use std::{collections::HashMap, sync::Arc};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::{mpsc, Mutex},
+};
+
+/// Tracks the set of connected replicas and fans propagated commands out
+/// to each of their write channels. Commands are carried as `Bytes` so
+/// broadcasting to N replicas is N reference-count bumps, not N copies.
+#[derive(Default)]
+pub struct ReplicaManager {
+    channels: HashMap<String, Arc<Mutex<ReplicaState>>>,
+}
+
+impl ReplicaManager {
+    pub fn add_channel(&mut self, id: String, state: Arc<Mutex<ReplicaState>>) {
+        self.channels.insert(id, state);
+    }
+
+    pub async fn broadcast(&self, command: Bytes) {
+        for state in self.channels.values() {
+            let tx = state.lock().await.tx.clone();
+            let _ = tx.send(command.clone()).await;
+        }
+    }
+}
+
+pub struct ReplicaState {
+    pub tx: mpsc::Sender<Bytes>,
+    pub ack_offset: usize,
+}
+
+impl ReplicaState {
+    pub fn new(tx: mpsc::Sender<Bytes>) -> Self {
+        Self { tx, ack_offset: 0 }
+    }
+}
+
+/// Drains propagated commands off `rx` and writes each one to the
+/// replica's connection in order. Generic over the transport so a
+/// TLS-secured replica link is handled the same as a plain TCP one.
+pub async fn replica_stream_handler<S>(
+    mut reader: StreamReader<S>,
+    mut rx: mpsc::Receiver<Bytes>,
+    state: Arc<Mutex<ReplicaState>>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    while let Some(command) = rx.recv().await {
+        if reader.write_all(&command).await.is_err() {
+            break;
+        }
+    }
+    let _ = state;
+}
@@ -0,0 +1,280 @@
+use super::{
+    context::ServerContext,
+    replica_link::{LinkState, ReplicationLink},
+    stream_reader::StreamReader,
+    tls::MaybeTlsStream,
+};
+use crate::{command::core::Command, protocol::Data};
+use std::{
+    io::{Error, ErrorKind, Result},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{net::TcpStream, sync::RwLock, time::sleep};
+use tokio_rustls::{rustls::pki_types::ServerName, TlsConnector};
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The transport a `ReplicaClient` speaks to its master over: plain TCP,
+/// or TLS when the config has `tls-enabled yes` (which also requires a
+/// `tls-ca-cert-file`).
+type ReplicaStream = MaybeTlsStream<TcpStream>;
+
+/// Drives the replica side of replication: performs the REPLCONF/PSYNC
+/// handshake against the configured master and, on connection loss or a
+/// handshake error, reconnects automatically with capped exponential
+/// backoff, resuming from the last replid/offset a prior `FULLRESYNC`
+/// gave it instead of forcing a full resync on every reconnect.
+pub struct ReplicaClient {
+    master_addr: String,
+    listening_port: u16,
+    context: ServerContext,
+    link: Arc<RwLock<ReplicationLink>>,
+    connector: Option<TlsConnector>,
+    replid: Option<String>,
+    offset: u64,
+}
+
+impl ReplicaClient {
+    pub fn new(
+        master_addr: String,
+        listening_port: u16,
+        context: ServerContext,
+        link: Arc<RwLock<ReplicationLink>>,
+        connector: Option<TlsConnector>,
+    ) -> Self {
+        Self {
+            master_addr,
+            listening_port,
+            context,
+            link,
+            connector,
+            replid: None,
+            offset: 0,
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move { self.run().await })
+    }
+
+    async fn run(mut self) {
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            self.set_state(LinkState::Connecting).await;
+            match self.connect_and_sync().await {
+                Ok(()) => backoff = INITIAL_BACKOFF,
+                Err(e) => self.record_error(e.to_string()).await,
+            }
+            // Whether the link ended in an error or just closed cleanly,
+            // back off before the next attempt so a master that's down
+            // (or repeatedly closing the connection) isn't hammered.
+            self.set_state(LinkState::Down).await;
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    async fn connect_and_sync(&mut self) -> Result<()> {
+        let tcp = TcpStream::connect(&self.master_addr).await?;
+        let stream = match &self.connector {
+            Some(connector) => {
+                let host = self
+                    .master_addr
+                    .rsplit_once(':')
+                    .map_or(self.master_addr.as_str(), |(host, _)| host);
+                let server_name = ServerName::try_from(host.to_string())
+                    .map_err(|_| Error::new(ErrorKind::InvalidInput, "invalid master host for TLS"))?;
+                let tls_stream = connector.connect(server_name, tcp).await?;
+                MaybeTlsStream::TlsClient(Box::new(tls_stream))
+            }
+            None => MaybeTlsStream::Plain(tcp),
+        };
+        let mut reader = StreamReader::new(stream);
+        self.set_state(LinkState::Syncing).await;
+        self.perform_handshake(&mut reader).await?;
+        self.set_state(LinkState::Connected).await;
+        self.stream_replicated_commands(&mut reader).await
+    }
+
+    /// Runs the REPLCONF/PSYNC handshake against an already-connected
+    /// master, mirroring the inbound side handled by `handlers::psync` on
+    /// the master. Requests a partial resync from the last replid/offset
+    /// we have on hand, or a full resync (`? -1`) when none has been
+    /// received yet. If the master answers a partial request with
+    /// `FULLRESYNC`, the RDB that follows is taken as a full resync; any
+    /// other reply is an error and the stored replid/offset are kept for
+    /// the next attempt.
+    async fn perform_handshake(&mut self, reader: &mut StreamReader<ReplicaStream>) -> Result<()> {
+        reader.write_all(b"*1\r\n$4\r\nPING\r\n").await?;
+        expect_reply(reader).await?;
+
+        let port = self.listening_port.to_string();
+        let replconf_port = format!(
+            "*3\r\n$8\r\nREPLCONF\r\n$14\r\nlistening-port\r\n${}\r\n{port}\r\n",
+            port.len()
+        );
+        reader.write_all(replconf_port.as_bytes()).await?;
+        expect_reply(reader).await?;
+
+        reader
+            .write_all(b"*3\r\n$8\r\nREPLCONF\r\n$4\r\ncapa\r\n$3\r\neof\r\n")
+            .await?;
+        expect_reply(reader).await?;
+
+        let (replid_arg, offset_arg) = match &self.replid {
+            Some(replid) => (replid.clone(), self.offset.to_string()),
+            None => ("?".to_string(), "-1".to_string()),
+        };
+        let psync = format!(
+            "*3\r\n$5\r\nPSYNC\r\n${}\r\n{replid_arg}\r\n${}\r\n{offset_arg}\r\n",
+            replid_arg.len(),
+            offset_arg.len()
+        );
+        reader.write_all(psync.as_bytes()).await?;
+
+        match expect_psync_reply(reader).await? {
+            PsyncReply::FullResync { replid, offset } => {
+                skip_rdb_payload(reader).await?;
+                self.replid = Some(replid);
+                self.offset = offset;
+            }
+            PsyncReply::Continue => {}
+        }
+        Ok(())
+    }
+
+    /// Applies propagated commands from the master to local state until
+    /// the connection drops, tracking how many on-wire bytes have been
+    /// consumed so a reconnect can resume from `self.offset` instead of
+    /// forcing a fresh full resync.
+    async fn stream_replicated_commands(&mut self, reader: &mut StreamReader<ReplicaStream>) -> Result<()> {
+        loop {
+            match reader.next_frame().await? {
+                Some((data, raw)) => {
+                    self.offset += raw.len() as u64;
+                    self.context
+                        .execute_command(Command::from_frame(data, raw))
+                        .await;
+                }
+                None => return Ok(()),
+            }
+        }
+    }
+
+    async fn set_state(&self, state: LinkState) {
+        self.link.write().await.state = state;
+    }
+
+    async fn record_error(&self, error: String) {
+        let mut link = self.link.write().await;
+        link.state = LinkState::Down;
+        link.last_error = Some(error);
+        link.retry_count += 1;
+    }
+}
+
+/// Reads one reply frame, treating a clean close mid-handshake as a
+/// failure rather than a no-op so the caller backs off and retries
+/// instead of pressing on against a dead connection.
+async fn expect_reply(reader: &mut StreamReader<ReplicaStream>) -> Result<()> {
+    match reader.next_frame().await? {
+        Some(_) => Ok(()),
+        None => Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "master closed connection during handshake",
+        )),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum PsyncReply {
+    FullResync { replid: String, offset: u64 },
+    Continue,
+}
+
+/// Parses the master's reply to `PSYNC`: either `+FULLRESYNC <replid>
+/// <offset>` (a fresh RDB follows) or `+CONTINUE` (the master accepted
+/// our offset and will stream commands from there with no RDB).
+async fn expect_psync_reply(reader: &mut StreamReader<ReplicaStream>) -> Result<PsyncReply> {
+    let reply = match reader.next_frame().await? {
+        Some((Data::BStr(reply), _)) => reply,
+        Some(_) => return Err(Error::new(ErrorKind::InvalidData, "unexpected PSYNC reply")),
+        None => {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "master closed connection during handshake",
+            ))
+        }
+    };
+    parse_psync_reply(reply.as_str())
+}
+
+/// Pure parsing logic behind `expect_psync_reply`, split out so it's
+/// testable without a live connection.
+fn parse_psync_reply(reply: &str) -> Result<PsyncReply> {
+    let mut parts = reply.split_whitespace();
+    match parts.next() {
+        Some("FULLRESYNC") => {
+            let replid = parts
+                .next()
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, "missing FULLRESYNC replid"))?
+                .to_string();
+            let offset = parts
+                .next()
+                .and_then(|offset| offset.parse().ok())
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, "missing FULLRESYNC offset"))?;
+            Ok(PsyncReply::FullResync { replid, offset })
+        }
+        Some("CONTINUE") => Ok(PsyncReply::Continue),
+        _ => Err(Error::new(ErrorKind::InvalidData, "unexpected PSYNC reply")),
+    }
+}
+
+/// Consumes the RDB snapshot a full resync sends as `$<len>\r\n` followed
+/// by exactly `len` raw bytes with no trailing CRLF — distinct from a
+/// normal RESP bulk string, which `StreamReader::next_frame` can't parse.
+async fn skip_rdb_payload(reader: &mut StreamReader<ReplicaStream>) -> Result<()> {
+    let header = reader.read_line().await?;
+    let header = std::str::from_utf8(&header)
+        .map_err(|_| Error::new(ErrorKind::InvalidData, "malformed RDB length header"))?;
+    let len: usize = header
+        .strip_prefix('$')
+        .and_then(|len| len.parse().ok())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "malformed RDB length header"))?;
+    reader.read_raw(len).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fullresync_reply() {
+        let reply = parse_psync_reply("FULLRESYNC abc123 42").unwrap();
+        assert_eq!(
+            reply,
+            PsyncReply::FullResync {
+                replid: "abc123".to_string(),
+                offset: 42,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_continue_reply() {
+        assert_eq!(parse_psync_reply("CONTINUE").unwrap(), PsyncReply::Continue);
+    }
+
+    #[test]
+    fn rejects_fullresync_missing_offset() {
+        assert!(parse_psync_reply("FULLRESYNC abc123").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_reply() {
+        assert!(parse_psync_reply("NOPE").is_err());
+    }
+}
@@ -0,0 +1,18 @@
+/// Observable state of this node's link to its replication master,
+/// surfaced through the `INFO replication` section so operators can see
+/// whether a replica has converged without digging through logs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum LinkState {
+    #[default]
+    Down,
+    Connecting,
+    Syncing,
+    Connected,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ReplicationLink {
+    pub state: LinkState,
+    pub last_error: Option<String>,
+    pub retry_count: u32,
+}
@@ -0,0 +1,130 @@
+use crate::protocol::{parse_frame, Data};
+use bytes::{Buf, Bytes, BytesMut};
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Buffered reader/writer over a client or replica connection. Generic
+/// over the underlying transport so the same parsing and propagation
+/// logic works whether the socket is a plain `TcpStream` or a
+/// TLS-wrapped stream.
+///
+/// Incoming bytes accumulate in a reusable `BytesMut` so a command split
+/// across TCP reads keeps the bytes already received and its `Data` is
+/// built once its final byte arrives. The check for a complete frame is
+/// not incremental: after every read it scans the buffered headers again
+/// from the start of the buffer.
+pub struct StreamReader<S> {
+    stream: S,
+    buffer: BytesMut,
+}
+
+impl<S> StreamReader<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            buffer: BytesMut::with_capacity(4 * 1024),
+        }
+    }
+
+    /// Reads the next complete RESP frame off the connection. Returns
+    /// `Ok(None)` on a clean EOF. The `Bytes` half of the result is the
+    /// exact on-wire slice consumed for this frame, shared rather than
+    /// copied from the receive buffer.
+    pub async fn next_frame(&mut self) -> io::Result<Option<(Data, Bytes)>> {
+        loop {
+            if let Some(frame) = parse_frame(&mut self.buffer)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?
+            {
+                return Ok(Some(frame));
+            }
+            if self.stream.read_buf(&mut self.buffer).await? == 0 {
+                return Ok(None);
+            }
+        }
+    }
+
+    pub async fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.stream.write_all(buf).await
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Reads and consumes a single CRLF-terminated line, excluding the
+    /// terminator. Used for inline replies that aren't followed by a
+    /// normal RESP frame, e.g. the `$<len>` length header the master
+    /// sends ahead of a raw RDB payload during a full resync.
+    pub async fn read_line(&mut self) -> io::Result<Bytes> {
+        loop {
+            if let Some(pos) = self
+                .buffer
+                .windows(2)
+                .position(|window| window == b"\r\n")
+            {
+                let line = self.buffer.split_to(pos).freeze();
+                self.buffer.advance(2);
+                return Ok(line);
+            }
+            if self.stream.read_buf(&mut self.buffer).await? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "connection closed while reading a line",
+                ));
+            }
+        }
+    }
+
+    /// Reads and consumes exactly `len` raw bytes with no RESP framing —
+    /// needed for the RDB payload a full resync sends as `$<len>` bytes
+    /// with no trailing CRLF.
+    pub async fn read_raw(&mut self, len: usize) -> io::Result<Bytes> {
+        while self.buffer.len() < len {
+            if self.stream.read_buf(&mut self.buffer).await? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "connection closed while reading raw bytes",
+                ));
+            }
+        }
+        Ok(self.buffer.split_to(len).freeze())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn next_frame_waits_out_a_split_write() {
+        let (mut client, server) = tokio::io::duplex(64);
+        let mut reader = StreamReader::new(server);
+
+        client.write_all(b"*1\r\n$4\r\nPI").await.unwrap();
+        let next = tokio::time::timeout(std::time::Duration::from_millis(50), reader.next_frame())
+            .await;
+        assert!(next.is_err(), "next_frame should still be waiting on the rest of the frame");
+
+        client.write_all(b"NG\r\n").await.unwrap();
+        let (data, raw) = reader.next_frame().await.unwrap().unwrap();
+        assert_eq!(raw.as_ref(), &b"*1\r\n$4\r\nPING\r\n"[..]);
+        match data {
+            Data::Array(items) => match &items[0] {
+                Data::BStr(s) => assert_eq!(s.as_str(), "PING"),
+                other => panic!("expected a bulk string, got {other:?}"),
+            },
+            other => panic!("expected an array, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn next_frame_returns_none_on_clean_eof() {
+        let (client, server) = tokio::io::duplex(64);
+        drop(client);
+        let mut reader = StreamReader::new(server);
+        assert!(reader.next_frame().await.unwrap().is_none());
+    }
+}
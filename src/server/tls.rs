@@ -0,0 +1,137 @@
+use std::{
+    io,
+    path::PathBuf,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_rustls::{client, server, TlsAcceptor, TlsConnector};
+
+/// TLS settings read from the config file: whether to terminate/originate
+/// TLS at all, the server cert/key for inbound clients, and the CA bundle
+/// used to verify an upstream master when acting as a replica.
+#[derive(Clone, Debug, Default)]
+pub struct TlsSettings {
+    pub enabled: bool,
+    pub cert_path: Option<PathBuf>,
+    pub key_path: Option<PathBuf>,
+    pub ca_path: Option<PathBuf>,
+}
+
+impl TlsSettings {
+    /// Builds the acceptor used to terminate TLS on inbound client
+    /// connections, from the configured cert/key pair.
+    pub fn build_acceptor(&self) -> io::Result<Option<TlsAcceptor>> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        let (Some(cert_path), Some(key_path)) = (&self.cert_path, &self.key_path) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "tls enabled but cert/key path missing",
+            ));
+        };
+        let certs = load_certs(cert_path)?;
+        let key = load_key(key_path)?;
+        let server_config = tokio_rustls::rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Some(TlsAcceptor::from(std::sync::Arc::new(server_config))))
+    }
+
+    /// Builds the connector used when this node, acting as a replica,
+    /// dials its master over TLS and verifies it against the CA bundle.
+    pub fn build_connector(&self) -> io::Result<Option<TlsConnector>> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        let Some(ca_path) = &self.ca_path else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "tls enabled but tls-ca-cert-file missing",
+            ));
+        };
+        let mut roots = tokio_rustls::rustls::RootCertStore::empty();
+        for cert in load_certs(ca_path)? {
+            roots
+                .add(cert)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+        let client_config = tokio_rustls::rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Ok(Some(TlsConnector::from(std::sync::Arc::new(client_config))))
+    }
+}
+
+fn load_certs(
+    path: &PathBuf,
+) -> io::Result<Vec<tokio_rustls::rustls::pki_types::CertificateDer<'static>>> {
+    let file = std::fs::File::open(path)?;
+    let mut reader = std::io::BufReader::new(file);
+    rustls_pemfile::certs(&mut reader).collect()
+}
+
+fn load_key(
+    path: &PathBuf,
+) -> io::Result<tokio_rustls::rustls::pki_types::PrivateKeyDer<'static>> {
+    let file = std::fs::File::open(path)?;
+    let mut reader = std::io::BufReader::new(file);
+    rustls_pemfile::private_key(&mut reader)?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no private key found"))
+}
+
+/// Either a plain transport or one wrapped in TLS. Implements the same
+/// `AsyncRead + AsyncWrite` bounds as the plain stream so the accept
+/// loop and the replica client don't need to special-case encrypted
+/// connections once the handshake is done.
+pub enum MaybeTlsStream<S> {
+    Plain(S),
+    TlsServer(Box<server::TlsStream<S>>),
+    TlsClient(Box<client::TlsStream<S>>),
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncRead for MaybeTlsStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            MaybeTlsStream::Plain(s) => Pin::new(s).poll_read(cx, buf),
+            MaybeTlsStream::TlsServer(s) => Pin::new(s).poll_read(cx, buf),
+            MaybeTlsStream::TlsClient(s) => Pin::new(s).poll_read(cx, buf),
+        }
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncWrite for MaybeTlsStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            MaybeTlsStream::Plain(s) => Pin::new(s).poll_write(cx, buf),
+            MaybeTlsStream::TlsServer(s) => Pin::new(s).poll_write(cx, buf),
+            MaybeTlsStream::TlsClient(s) => Pin::new(s).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            MaybeTlsStream::Plain(s) => Pin::new(s).poll_flush(cx),
+            MaybeTlsStream::TlsServer(s) => Pin::new(s).poll_flush(cx),
+            MaybeTlsStream::TlsClient(s) => Pin::new(s).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            MaybeTlsStream::Plain(s) => Pin::new(s).poll_shutdown(cx),
+            MaybeTlsStream::TlsServer(s) => Pin::new(s).poll_shutdown(cx),
+            MaybeTlsStream::TlsClient(s) => Pin::new(s).poll_shutdown(cx),
+        }
+    }
+}